@@ -1,15 +1,18 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
+    clock::Clock,
     entrypoint,
     entrypoint::ProgramResult,
-    program::invoke_signed,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
     pubkey::Pubkey,
-    system_instruction::create_account,
+    system_instruction::{create_account, transfer, MAX_PERMITTED_DATA_LENGTH},
     system_program::ID as SYSTEM_PROGRAM_ID,
     sysvar::rent::Rent,
     sysvar::Sysvar,
 };
+use std::collections::HashMap;
 
 solana_program::declare_id!("3VG4GdkTVETFrpmfCMVoGr4G73rh4hkPrB9vQUKyPNx5");
 entrypoint!(process_instruction);
@@ -21,6 +24,7 @@ pub enum TodoInstruction {
         title: [u8; 64],
         description: [u8; 64],
         bump: u8,
+        body_len: u64,
     },
     UpdateTask {
         id: u64,
@@ -30,19 +34,100 @@ pub enum TodoInstruction {
     DeleteTask {
         id: u64,
     },
+    BatchTasks {
+        ops: Vec<TaskOp>,
+    },
+    WriteTask {
+        id: u64,
+        offset: u64,
+        data: Vec<u8>,
+    },
+    ResizeTask {
+        id: u64,
+        new_len: u64,
+    },
+    MigrateTask {
+        id: u64,
+    },
+}
+
+/// A single CRUD operation inside a `BatchTasks` instruction. Mirrors the
+/// single-op instruction variants so each op carries everything needed to
+/// re-derive and validate its own Task PDA.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub enum TaskOp {
+    Create {
+        id: u64,
+        title: [u8; 64],
+        description: [u8; 64],
+        bump: u8,
+        body_len: u64,
+    },
+    Update {
+        id: u64,
+        title: Option<[u8; 64]>,
+        description: Option<[u8; 64]>,
+    },
+    Delete {
+        id: u64,
+    },
 }
 
 #[derive(BorshSerialize, BorshDeserialize)]
 pub struct Task {
-    title: [u8; 64],       // 64 +
-    description: [u8; 64], // 64 +
-    authority: Pubkey,     // 32 +
-    id: u64,               // 8 +
-    bump: u8,              // 1 = 169 bytes
+    discriminator: [u8; 8], // 8 +
+    version: u8,            // 1 +
+    authority: Pubkey,      // 32 +
+    id: u64,                // 8 +
+    bump: u8,               // 1 +
+    created_at: i64,        // 8 +
+    completed: bool,        // 1 = 59 bytes (header)
+    title: [u8; 64],        // 64 +
+    description: [u8; 64],  // 64 = 128 bytes (content)
+    body: Vec<u8>,          // 4-byte len prefix + N bytes (variable, resizable content)
+}
+
+// The pre-`MigrateTask` on-disk layout: `version` is always 0, and there
+// are no `created_at`/`completed` fields. Accounts created before this
+// schema upgrade are in this shape until `MigrateTask` rewrites them; kept
+// around solely so `Task::deserialize_versioned` can still read them.
+#[derive(BorshSerialize, BorshDeserialize)]
+struct TaskV0 {
+    discriminator: [u8; 8],
+    version: u8,
+    authority: Pubkey,
+    id: u64,
+    bump: u8,
+    title: [u8; 64],
+    description: [u8; 64],
+    body: Vec<u8>,
 }
 
 impl Task {
-    const LEN: usize = 169; // 64 + 64 + 32 + 8 + 1
+    // First 8 bytes of sha256("account:Task"), following Anchor's `init`
+    // account-discriminator convention. Written once by `CreateTask` and
+    // checked by every other instruction before trusting an account's data.
+    const DISCRIMINATOR: [u8; 8] = [79, 34, 229, 55, 88, 90, 55, 84];
+    // The schema version `CreateTask` and `MigrateTask` always write.
+    // `deserialize_versioned` branches on this exact byte (right after
+    // `DISCRIMINATOR`) to pick `Task` vs. `TaskV0`, rather than guessing
+    // from whichever layout happens to parse.
+    const CURRENT_VERSION: u8 = 1;
+    const LEGACY_VERSION: u8 = 0;
+    // Minimum size: an empty `body` still costs its 4-byte length prefix.
+    // Accounts are sized as `Task::LEN + body.len()` and grow/shrink via `ResizeTask`.
+    const LEN: usize = 191; // 59 (header) + 128 (content) + 4 (empty body prefix)
+    // Fixed prefix (discriminator/version/authority/id/bump/created_at/
+    // completed) that `WriteTask` must never touch.
+    const HEADER_LEN: usize = 59; // 8 + 1 + 32 + 8 + 1 + 8 + 1
+    // `title`+`description`, directly after `HEADER_LEN`. `WriteTask` may
+    // freely address any byte in here.
+    const CONTENT_LEN: usize = 128; // 64 + 64
+    // `body`'s own Borsh `Vec<u8>` length prefix, directly after
+    // `CONTENT_LEN`, for every task regardless of body length. `WriteTask`
+    // must never let a caller-chosen offset land here, or the account
+    // becomes permanently unreadable by every other instruction.
+    const BODY_PREFIX_LEN: usize = 4;
     const TAG: &'static str = "task";
 
     pub fn new(
@@ -51,14 +136,81 @@ impl Task {
         description: [u8; 64],
         authority: Pubkey,
         bump: u8,
+        created_at: i64,
+        body: Vec<u8>,
     ) -> Self {
         Self {
+            discriminator: Self::DISCRIMINATOR,
+            version: Self::CURRENT_VERSION,
             id,
             title,
             description,
             authority,
             bump,
+            created_at,
+            completed: false,
+            body,
+        }
+    }
+
+    /// Deserializes `data` regardless of on-disk schema version, by
+    /// branching on the leading version byte (right after `DISCRIMINATOR`)
+    /// rather than guessing from whichever layout happens to parse: `0`
+    /// reads the pre-migration `TaskV0` layout (defaulting the fields it
+    /// lacks), `Task::CURRENT_VERSION` reads the current `Task` layout
+    /// directly. Lets old accounts keep parsing until `MigrateTask` runs.
+    pub fn deserialize_versioned(data: &[u8]) -> Result<Task, ProgramError> {
+        if data.len() < Self::DISCRIMINATOR.len()
+            || data[..Self::DISCRIMINATOR.len()] != Self::DISCRIMINATOR
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let version = *data
+            .get(Self::DISCRIMINATOR.len())
+            .ok_or(ProgramError::InvalidAccountData)?;
+
+        match version {
+            Self::LEGACY_VERSION => {
+                let legacy =
+                    TaskV0::try_from_slice(data).map_err(|_| ProgramError::InvalidAccountData)?;
+                Ok(Task {
+                    discriminator: legacy.discriminator,
+                    version: Self::LEGACY_VERSION,
+                    authority: legacy.authority,
+                    id: legacy.id,
+                    bump: legacy.bump,
+                    created_at: 0,
+                    completed: false,
+                    title: legacy.title,
+                    description: legacy.description,
+                    body: legacy.body,
+                })
+            }
+            Self::CURRENT_VERSION => {
+                Task::try_from_slice(data).map_err(|_| ProgramError::InvalidAccountData)
+            }
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+
+    /// Returns `Ok(())` if `task` is owned by `program_id` and its data
+    /// begins with the `Task` discriminator, `Err(ProgramError::InvalidAccountData)`
+    /// otherwise. Callers must check this before trusting a deserialized `Task`.
+    fn verify_initialized(
+        task: &AccountInfo,
+        program_id: &Pubkey,
+        task_raw_bytes: &[u8],
+    ) -> ProgramResult {
+        if *task.owner != *program_id {
+            return Err(ProgramError::InvalidAccountData);
         }
+        if task_raw_bytes.len() < Self::DISCRIMINATOR.len()
+            || task_raw_bytes[..Self::DISCRIMINATOR.len()] != Self::DISCRIMINATOR
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
     }
 
     pub fn create_pda(program_id: &Pubkey, id: u64, authority: &Pubkey, bump: u8) -> Pubkey {
@@ -76,164 +228,531 @@ impl Task {
     }
 }
 
+// Accounts.
+// - 1. [WRITE, SIGNER]   Authority and Payer.
+// - 2. [WRITE]           Task.
+// - 3. []                System Program.
+fn create_task<'a, 'b>(
+    accounts_iter: &mut std::slice::Iter<'a, AccountInfo<'b>>,
+    program_id: &Pubkey,
+    id: u64,
+    title: [u8; 64],
+    description: [u8; 64],
+    bump: u8,
+    body_len: u64,
+) -> ProgramResult {
+    let authority = next_account_info(accounts_iter)?;
+    let task = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Simple assert validations.
+    assert!(authority.is_signer);
+    assert!(authority.is_writable);
+    assert!(!task.is_signer); // NOT
+    assert!(task.is_writable);
+    assert!(*system_program.key == SYSTEM_PROGRAM_ID);
+
+    // Reject reinitialization: a funded account, or one this program already
+    // owns and has stamped with the Task discriminator, was already created.
+    if task.lamports() > 0
+        || (*task.owner == *program_id
+            && task.data_len() >= Task::DISCRIMINATOR.len()
+            && task.try_borrow_data()?[..Task::DISCRIMINATOR.len()] == Task::DISCRIMINATOR)
+    {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    // PDA: Program Derived Address.
+    let (task_pda, task_bump) = Pubkey::find_program_address(
+        &[
+            Task::TAG.as_bytes(),
+            &id.to_le_bytes(),
+            authority.key.as_ref(),
+        ],
+        program_id,
+    );
+
+    // Business Logic.
+
+    // Create Task account with System Program. `body_len` is
+    // caller-controlled, so bound it (and catch overflow) before it's used
+    // for allocation — an unchecked huge value would abort the program
+    // instead of failing cleanly.
+    let task_space = (Task::LEN as u64)
+        .checked_add(body_len)
+        .filter(|space| *space <= MAX_PERMITTED_DATA_LENGTH)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    let task_rent = Rent::get()?.minimum_balance(task_space as usize);
+    invoke_signed(
+        &create_account(authority.key, &task_pda, task_rent, task_space, program_id),
+        &[authority.clone(), task.clone()],
+        &[&[
+            Task::TAG.as_bytes(),
+            &id.to_le_bytes(),
+            authority.key.as_ref(),
+            &[task_bump],
+        ]],
+    )?;
+
+    // The account is freshly allocated and zeroed, so it can't be
+    // deserialized as a `Task` yet (its length won't match an empty-body
+    // `Task` once `body_len` > 0) — build and serialize it fresh instead.
+    let created_at = Clock::get()?.unix_timestamp;
+    let task_data = Task::new(
+        id,
+        title,
+        description,
+        *authority.key,
+        bump,
+        created_at,
+        vec![0u8; body_len as usize],
+    );
+
+    let mut task_raw_bytes = task.try_borrow_mut_data()?;
+    task_data.serialize(&mut &mut task_raw_bytes[..])?;
+
+    Ok(())
+}
+
+// Accounts
+// - 1. [WRITE, SIGNER]   Authority and Payer.
+// - 2. [WRITE]           Task.
+fn update_task<'a, 'b>(
+    accounts_iter: &mut std::slice::Iter<'a, AccountInfo<'b>>,
+    program_id: &Pubkey,
+    id: u64,
+    title: Option<[u8; 64]>,
+    description: Option<[u8; 64]>,
+) -> ProgramResult {
+    let authority = next_account_info(accounts_iter)?;
+    let task = next_account_info(accounts_iter)?;
+
+    assert!(authority.is_signer);
+    assert!(authority.is_writable);
+    assert!(!task.is_signer); // NOT
+    assert!(task.is_writable);
+    assert!(title.is_some() || description.is_some());
+
+    let mut task_raw_bytes = task.try_borrow_mut_data()?;
+    Task::verify_initialized(task, program_id, &task_raw_bytes)?;
+    let mut task_data = Task::try_from_slice(&task_raw_bytes)?;
+
+    assert!(*authority.key == task_data.authority);
+    assert!(id == task_data.id);
+
+    let task_pda = Task::create_pda(
+        program_id,
+        task_data.id,
+        &task_data.authority,
+        task_data.bump,
+    );
+
+    assert!(*task.key == task_pda);
+
+    if title.is_some() {
+        task_data.title = [0u8; 64];
+        task_data.title = title.unwrap();
+    }
+    if description.is_some() {
+        task_data.description = [0u8; 64];
+        task_data.description = description.unwrap();
+    }
+
+    task_data.serialize(&mut &mut task_raw_bytes[..])?;
+
+    Ok(())
+}
+
+// Accounts.
+// - 1. [WRITE, SIGNER]   Authority and Payer.
+// - 2. [WRITE]           Task.
+//
+// Writes `data` directly into the Task's content region starting at
+// `offset`, bypassing a full Borsh re-serialization. The header
+// (discriminator/authority/id/bump) is never touched.
+fn write_task<'a, 'b>(
+    accounts_iter: &mut std::slice::Iter<'a, AccountInfo<'b>>,
+    program_id: &Pubkey,
+    id: u64,
+    offset: u64,
+    data: Vec<u8>,
+) -> ProgramResult {
+    let authority = next_account_info(accounts_iter)?;
+    let task = next_account_info(accounts_iter)?;
+
+    assert!(authority.is_signer);
+    assert!(authority.is_writable);
+    assert!(!task.is_signer); // NOT
+    assert!(task.is_writable);
+
+    let mut task_raw_bytes = task.try_borrow_mut_data()?;
+    Task::verify_initialized(task, program_id, &task_raw_bytes)?;
+    let task_data = Task::try_from_slice(&task_raw_bytes)?;
+
+    assert!(*authority.key == task_data.authority);
+    assert!(id == task_data.id);
+
+    let task_pda = Task::create_pda(
+        program_id,
+        task_data.id,
+        &task_data.authority,
+        task_data.bump,
+    );
+
+    assert!(*task.key == task_pda);
+
+    // `offset` is caller-facing and content-relative: `0..CONTENT_LEN`
+    // addresses `title`+`description` directly, and `CONTENT_LEN..`
+    // addresses `body`'s data, shifted past its Borsh length prefix so a
+    // write can never land on (and corrupt) that prefix. A write may not
+    // straddle the content/body boundary.
+    let offset = offset as usize;
+    let start = if offset < Task::CONTENT_LEN {
+        let content_end = offset
+            .checked_add(data.len())
+            .ok_or(ProgramError::AccountDataTooSmall)?;
+        if content_end > Task::CONTENT_LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Task::HEADER_LEN
+            .checked_add(offset)
+            .ok_or(ProgramError::AccountDataTooSmall)?
+    } else {
+        let body_offset = offset - Task::CONTENT_LEN;
+        Task::HEADER_LEN
+            .checked_add(Task::CONTENT_LEN)
+            .and_then(|v| v.checked_add(Task::BODY_PREFIX_LEN))
+            .and_then(|v| v.checked_add(body_offset))
+            .ok_or(ProgramError::AccountDataTooSmall)?
+    };
+    let end = start
+        .checked_add(data.len())
+        .ok_or(ProgramError::AccountDataTooSmall)?;
+
+    if end > task_raw_bytes.len() {
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+
+    task_raw_bytes[start..end].copy_from_slice(&data);
+
+    Ok(())
+}
+
+// Accounts.
+// - 1. [WRITE, SIGNER]   Authority and Payer.
+// - 2. [WRITE]           Task.
+// - 3. []                System Program.
+//
+// Resizes `body` to exactly `new_len`, zero-extending on growth and
+// truncating on shrink, then reallocs the account and tops up or refunds
+// lamports so it stays exactly rent-exempt.
+fn resize_task<'a, 'b>(
+    accounts_iter: &mut std::slice::Iter<'a, AccountInfo<'b>>,
+    program_id: &Pubkey,
+    id: u64,
+    new_len: u64,
+) -> ProgramResult {
+    let authority = next_account_info(accounts_iter)?;
+    let task = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    assert!(authority.is_signer);
+    assert!(authority.is_writable);
+    assert!(!task.is_signer); // NOT
+    assert!(task.is_writable);
+    assert!(*system_program.key == SYSTEM_PROGRAM_ID);
+
+    let task_raw_bytes = task.try_borrow_data()?;
+    Task::verify_initialized(task, program_id, &task_raw_bytes)?;
+    let mut task_data = Task::try_from_slice(&task_raw_bytes)?;
+    drop(task_raw_bytes);
+
+    assert!(*authority.key == task_data.authority);
+    assert!(id == task_data.id);
+
+    let task_pda = Task::create_pda(
+        program_id,
+        task_data.id,
+        &task_data.authority,
+        task_data.bump,
+    );
+
+    assert!(*task.key == task_pda);
+
+    // `new_len` is caller-controlled, so bound it (and catch overflow)
+    // before it's used to resize `body`/realloc the account — an unchecked
+    // huge value would abort the program instead of failing cleanly.
+    let new_space = (Task::LEN as u64)
+        .checked_add(new_len)
+        .filter(|space| *space <= MAX_PERMITTED_DATA_LENGTH)
+        .ok_or(ProgramError::InvalidInstructionData)? as usize;
+
+    task_data.body.resize(new_len as usize, 0u8);
+    task.realloc(new_space, false)?;
+
+    let new_rent = Rent::get()?.minimum_balance(new_space);
+    let current_lamports = task.lamports();
+
+    if new_rent > current_lamports {
+        let top_up = new_rent - current_lamports;
+        invoke(
+            &transfer(authority.key, task.key, top_up),
+            &[authority.clone(), task.clone(), system_program.clone()],
+        )?;
+    } else if new_rent < current_lamports {
+        let refund = current_lamports - new_rent;
+        // Direct transfer is okay since Task is a PDA owned by this program.
+        **task.try_borrow_mut_lamports()? = current_lamports - refund;
+        **authority.try_borrow_mut_lamports()? = authority
+            .lamports()
+            .checked_add(refund) // None if overflow.
+            .unwrap();
+    }
+
+    task_data.serialize(&mut &mut task.try_borrow_mut_data()?[..])?;
+
+    Ok(())
+}
+
+// Accounts.
+// - 1. [WRITE, SIGNER]   Authority and Payer.
+// - 2. [WRITE]           Task.
+// - 3. []                System Program.
+//
+// Reads a Task account regardless of its on-disk schema version (via
+// `Task::deserialize_versioned`), reallocs if the current schema is larger,
+// and rewrites it at `Task::CURRENT_VERSION` with defaults for any new
+// fields. A no-op once the account is already at the current version.
+fn migrate_task<'a, 'b>(
+    accounts_iter: &mut std::slice::Iter<'a, AccountInfo<'b>>,
+    program_id: &Pubkey,
+    id: u64,
+) -> ProgramResult {
+    let authority = next_account_info(accounts_iter)?;
+    let task = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    assert!(authority.is_signer);
+    assert!(authority.is_writable);
+    assert!(!task.is_signer); // NOT
+    assert!(task.is_writable);
+    assert!(*system_program.key == SYSTEM_PROGRAM_ID);
+
+    let task_raw_bytes = task.try_borrow_data()?;
+    Task::verify_initialized(task, program_id, &task_raw_bytes)?;
+    let mut task_data = Task::deserialize_versioned(&task_raw_bytes)?;
+    drop(task_raw_bytes);
+
+    assert!(*authority.key == task_data.authority);
+    assert!(id == task_data.id);
+
+    let task_pda = Task::create_pda(
+        program_id,
+        task_data.id,
+        &task_data.authority,
+        task_data.bump,
+    );
+
+    assert!(*task.key == task_pda);
+
+    if task_data.version == Task::CURRENT_VERSION {
+        // Already migrated; nothing to do.
+        return Ok(());
+    }
+
+    task_data.version = Task::CURRENT_VERSION;
+
+    let new_space = Task::LEN + task_data.body.len();
+    task.realloc(new_space, false)?;
+
+    let new_rent = Rent::get()?.minimum_balance(new_space);
+    let current_lamports = task.lamports();
+    if new_rent > current_lamports {
+        let top_up = new_rent - current_lamports;
+        invoke(
+            &transfer(authority.key, task.key, top_up),
+            &[authority.clone(), task.clone(), system_program.clone()],
+        )?;
+    }
+
+    task_data.serialize(&mut &mut task.try_borrow_mut_data()?[..])?;
+
+    Ok(())
+}
+
+// Accounts.
+// - 1. [WRITE, SIGNER]   Authority and Payer.
+// - 2. [WRITE]           Task.
+fn delete_task<'a, 'b>(
+    accounts_iter: &mut std::slice::Iter<'a, AccountInfo<'b>>,
+    program_id: &Pubkey,
+    id: u64,
+) -> ProgramResult {
+    let authority = next_account_info(accounts_iter)?;
+    let task = next_account_info(accounts_iter)?;
+
+    assert!(authority.is_signer);
+    assert!(authority.is_writable);
+    assert!(!task.is_signer); // NOT
+    assert!(task.is_writable);
+
+    let mut task_raw_bytes = task.try_borrow_mut_data()?;
+    Task::verify_initialized(task, program_id, &task_raw_bytes)?;
+    let task_data = Task::try_from_slice(&task_raw_bytes)?;
+
+    assert!(*authority.key == task_data.authority);
+    assert!(id == task_data.id);
+
+    let task_pda = Task::create_pda(
+        program_id,
+        task_data.id,
+        &task_data.authority,
+        task_data.bump,
+    );
+
+    assert!(*task.key == task_pda);
+
+    // Close the Task (PDA) account by Zeroing.
+    task_raw_bytes.fill(0);
+
+    let task_lamports = task.lamports();
+    let authority_lamports = authority.lamports();
+
+    // Direct transfer Task (PDA) lamports into authority.
+    // NOTE: Direct transfer is okay since Task is a PDA owned by this program.
+    **authority.try_borrow_mut_lamports()? = authority_lamports
+        .checked_add(task_lamports) // None if overflow.
+        .unwrap();
+
+    // Zero out Task (PDA) lamports.
+    //
+    // Runtime eventually cleans this account up due to 0 rent;
+    **task.try_borrow_mut_lamports()? = 0;
+
+    Ok(())
+}
+
+fn apply_task_op<'a, 'b>(
+    accounts_iter: &mut std::slice::Iter<'a, AccountInfo<'b>>,
+    program_id: &Pubkey,
+    op: TaskOp,
+) -> ProgramResult {
+    match op {
+        TaskOp::Create {
+            id,
+            title,
+            description,
+            bump,
+            body_len,
+        } => create_task(
+            accounts_iter,
+            program_id,
+            id,
+            title,
+            description,
+            bump,
+            body_len,
+        ),
+        TaskOp::Update {
+            id,
+            title,
+            description,
+        } => update_task(accounts_iter, program_id, id, title, description),
+        TaskOp::Delete { id } => delete_task(accounts_iter, program_id, id),
+    }
+}
+
+// Canonicalizes a flat account list so that every entry sharing a pubkey
+// resolves to the exact same `AccountInfo` clone (and therefore the same
+// underlying `Rc<RefCell<..>>` data/lamports buffers). A batch (or a
+// composing program doing CPI) may pass one account — e.g. the same Task
+// PDA, or an authority that also appears as a writable account elsewhere —
+// more than once in a single instruction; without this, ops could end up
+// routed through distinct `AccountInfo` values for the same key and
+// double-borrow its data or lamports, which panics.
+fn resolve_accounts<'a, 'b>(accounts: &'a [AccountInfo<'b>]) -> Vec<AccountInfo<'b>> {
+    let mut resolved: HashMap<Pubkey, AccountInfo<'b>> = HashMap::with_capacity(accounts.len());
+    accounts
+        .iter()
+        .map(|account| {
+            resolved
+                .entry(*account.key)
+                .or_insert_with(|| account.clone())
+                .clone()
+        })
+        .collect()
+}
+
 pub fn process_instruction(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     instruction_data: &[u8],
 ) -> ProgramResult {
     let instruction = TodoInstruction::try_from_slice(instruction_data)?;
+    let accounts = resolve_accounts(accounts);
 
     match instruction {
-        // Accounts.
-        // - 1. [WRITE, SIGNER]   Authority and Payer.
-        // - 2. [WRITE]           Task.
-        // - 3. []                System Program.
         TodoInstruction::CreateTask {
             id,
             title,
             description,
             bump,
+            body_len,
         } => {
             let accounts_iter = &mut accounts.iter();
-            let authority = next_account_info(accounts_iter)?;
-            let task = next_account_info(accounts_iter)?;
-            let system_program = next_account_info(accounts_iter)?;
-
-            // Simple assert validations.
-            assert!(authority.is_signer);
-            assert!(authority.is_writable);
-            assert!(!task.is_signer); // NOT
-            assert!(task.is_writable);
-            assert!(*system_program.key == SYSTEM_PROGRAM_ID);
-
-            // PDA: Program Derived Address.
-            let (task_pda, task_bump) = Pubkey::find_program_address(
-                &[
-                    Task::TAG.as_bytes(),
-                    &id.to_le_bytes(),
-                    authority.key.as_ref(),
-                ],
+            create_task(
+                accounts_iter,
                 program_id,
-            );
-
-            // Business Logic.
-
-            // Create Task account with System Program.
-            let task_rent = Rent::get()?.minimum_balance(Task::LEN);
-            let task_space: u64 = Task::LEN as u64;
-            invoke_signed(
-                &create_account(authority.key, &task_pda, task_rent, task_space, program_id),
-                &[authority.clone(), task.clone()],
-                &[&[
-                    Task::TAG.as_bytes(),
-                    &id.to_le_bytes(),
-                    authority.key.as_ref(),
-                    &[task_bump],
-                ]],
+                id,
+                title,
+                description,
+                bump,
+                body_len,
             )?;
-
-            let mut task_raw_bytes = task.try_borrow_mut_data()?;
-            let mut task_data = Task::try_from_slice(&task_raw_bytes)?;
-            task_data.title = [0u8; 64];
-            task_data.description = [0u8; 64];
-            task_data.title = title;
-            task_data.description = description;
-            task_data.authority = *authority.key;
-            task_data.id = id;
-            task_data.bump = bump;
-
-            task_data.serialize(&mut &mut task_raw_bytes[..])?;
         }
 
-        // Accounts
-        // - 1. [WRITE, SIGNER]   Authority and Payer.
-        // - 2. [WRITE]           Task.
         TodoInstruction::UpdateTask {
             id,
             title,
             description,
         } => {
             let accounts_iter = &mut accounts.iter();
-            let authority = next_account_info(accounts_iter)?;
-            let task = next_account_info(accounts_iter)?;
-
-            assert!(authority.is_signer);
-            assert!(authority.is_writable);
-            assert!(!task.is_signer); // NOT
-            assert!(task.is_writable);
-            assert!(title.is_some() || description.is_some());
-
-            let mut task_raw_bytes = task.try_borrow_mut_data()?;
-            let mut task_data = Task::try_from_slice(&task_raw_bytes)?;
-
-            assert!(*authority.key == task_data.authority);
-            assert!(id == task_data.id);
-
-            let task_pda = Task::create_pda(
-                &program_id,
-                task_data.id,
-                &task_data.authority,
-                task_data.bump,
-            );
-
-            assert!(*task.key == task_pda);
-
-            if title.is_some() {
-                task_data.title = [0u8; 64];
-                task_data.title = title.unwrap();
-            }
-            if description.is_some() {
-                task_data.description = [0u8; 64];
-                task_data.description = description.unwrap();
-            }
-
-            task_data.serialize(&mut &mut task_raw_bytes[..])?;
+            update_task(accounts_iter, program_id, id, title, description)?;
         }
 
-        // Accounts.
-        // - 1. [WRITE, SIGNER]   Authority and Payer.
-        // - 2. [WRITE]           Task.
         TodoInstruction::DeleteTask { id } => {
             let accounts_iter = &mut accounts.iter();
-            let authority = next_account_info(accounts_iter)?;
-            let task = next_account_info(accounts_iter)?;
-
-            assert!(authority.is_signer);
-            assert!(authority.is_writable);
-            assert!(!task.is_signer); // NOT
-            assert!(task.is_writable);
-
-            let mut task_raw_bytes = task.try_borrow_mut_data()?;
-            let task_data = Task::try_from_slice(&task_raw_bytes)?;
-
-            assert!(*authority.key == task_data.authority);
-            assert!(id == task_data.id);
-
-            let task_pda = Task::create_pda(
-                &program_id,
-                task_data.id,
-                &task_data.authority,
-                task_data.bump,
-            );
-
-            assert!(*task.key == task_pda);
+            delete_task(accounts_iter, program_id, id)?;
+        }
 
-            // Close the Task (PDA) account by Zeroing.
-            task_raw_bytes.fill(0);
+        TodoInstruction::WriteTask { id, offset, data } => {
+            let accounts_iter = &mut accounts.iter();
+            write_task(accounts_iter, program_id, id, offset, data)?;
+        }
 
-            let task_lamports = task.lamports();
-            let authority_lamports = authority.lamports();
+        TodoInstruction::ResizeTask { id, new_len } => {
+            let accounts_iter = &mut accounts.iter();
+            resize_task(accounts_iter, program_id, id, new_len)?;
+        }
 
-            // Direct transfer Task (PDA) lamports into authority.
-            // NOTE: Direct transfer is okay since Task is a PDA owned by authority.
-            **authority.try_borrow_mut_lamports()? = authority_lamports
-                .checked_add(task_lamports) // None if overflow.
-                .unwrap();
+        TodoInstruction::MigrateTask { id } => {
+            let accounts_iter = &mut accounts.iter();
+            migrate_task(accounts_iter, program_id, id)?;
+        }
 
-            // Zero out Task (PDA) lamports.
-            //
-            // Runtime eventually cleans this account up due to 0 rent;
-            **task.try_borrow_mut_lamports()? = 0;
+        // Accounts.
+        // Flat list of per-op accounts, consumed in order. Each op pulls
+        // exactly the accounts its variant needs (see `create_task`/
+        // `update_task`/`delete_task`), so a client lays out accounts as the
+        // concatenation of each op's own account list, in `ops` order.
+        //
+        // Executed against a single instruction, so a failing op aborts the
+        // whole transaction and none of the preceding ops are applied.
+        TodoInstruction::BatchTasks { ops } => {
+            let accounts_iter = &mut accounts.iter();
+            for op in ops {
+                apply_task_op(accounts_iter, program_id, op)?;
+            }
         }
     };
 
@@ -302,6 +821,7 @@ mod tests {
             title,
             description,
             bump: task_bump,
+            body_len: 0,
         };
 
         let mut create_task_ix_data = Vec::new();
@@ -356,7 +876,7 @@ mod tests {
         );
 
         // Inject Account that simulates CreateTask.
-        let old_task = Task::new(id, title, description, authority, task_bump);
+        let old_task = Task::new(id, title, description, authority, task_bump, 0, vec![]);
         let mut account_data: Vec<u8> = Vec::new();
         old_task.serialize(&mut account_data).unwrap();
         let injected_task_account = Account {
@@ -430,7 +950,7 @@ mod tests {
         );
 
         // Inject Account that simulates CreateTask.
-        let old_task = Task::new(id, title, description, authority, task_bump);
+        let old_task = Task::new(id, title, description, authority, task_bump, 0, vec![]);
         let mut account_data: Vec<u8> = Vec::new();
         old_task.serialize(&mut account_data).unwrap();
         let injected_task_account = Account {
@@ -478,6 +998,1043 @@ mod tests {
         assert!(task_account.is_none());
     }
 
+    #[tokio::test]
+    async fn test_batch_tasks_mixed() {
+        let program_id = Pubkey::new_unique();
+        let (title, description, new_title, new_description) = get_env();
+
+        let create_id: u64 = 1;
+        let update_id: u64 = 2;
+        let delete_id: u64 = 3;
+
+        let authority_keypair = Keypair::new();
+        let authority = authority_keypair.pubkey();
+
+        let mut test = ProgramTest::default();
+        test.add_program("todo", program_id, None);
+
+        let (update_pda, update_bump) = Pubkey::find_program_address(
+            &[
+                Task::TAG.as_bytes(),
+                &update_id.to_le_bytes(),
+                authority.as_ref(),
+            ],
+            &program_id,
+        );
+        let update_task = Task::new(
+            update_id,
+            title,
+            description,
+            authority,
+            update_bump,
+            0,
+            vec![],
+        );
+        let mut update_account_data = Vec::new();
+        update_task.serialize(&mut update_account_data).unwrap();
+        test.add_account(
+            update_pda,
+            Account {
+                lamports: u32::MAX as u64,
+                owner: program_id,
+                executable: false,
+                rent_epoch: Default::default(),
+                data: update_account_data,
+            },
+        );
+
+        let (delete_pda, delete_bump) = Pubkey::find_program_address(
+            &[
+                Task::TAG.as_bytes(),
+                &delete_id.to_le_bytes(),
+                authority.as_ref(),
+            ],
+            &program_id,
+        );
+        let delete_task = Task::new(
+            delete_id,
+            title,
+            description,
+            authority,
+            delete_bump,
+            0,
+            vec![],
+        );
+        let mut delete_account_data = Vec::new();
+        delete_task.serialize(&mut delete_account_data).unwrap();
+        test.add_account(
+            delete_pda,
+            Account {
+                lamports: u32::MAX as u64,
+                owner: program_id,
+                executable: false,
+                rent_epoch: Default::default(),
+                data: delete_account_data,
+            },
+        );
+
+        let ctx = test.start_with_context().await;
+
+        // The Create op is paid for by `ctx.payer` (it needs a funded
+        // account to cover the new Task's rent), same as `test_create_task`.
+        let (create_pda, create_bump) = Pubkey::find_program_address(
+            &[
+                Task::TAG.as_bytes(),
+                &create_id.to_le_bytes(),
+                ctx.payer.pubkey().as_ref(),
+            ],
+            &program_id,
+        );
+
+        let batch_ix = TodoInstruction::BatchTasks {
+            ops: vec![
+                TaskOp::Create {
+                    id: create_id,
+                    title,
+                    description,
+                    bump: create_bump,
+                    body_len: 0,
+                },
+                TaskOp::Update {
+                    id: update_id,
+                    title: Some(new_title),
+                    description: Some(new_description),
+                },
+                TaskOp::Delete { id: delete_id },
+            ],
+        };
+
+        let mut batch_ix_data = Vec::new();
+        batch_ix.serialize(&mut batch_ix_data).unwrap();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: vec![
+                    // Create { authority, task, system_program }
+                    AccountMeta::new(ctx.payer.pubkey(), true),
+                    AccountMeta::new(create_pda, false),
+                    AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                    // Update { authority, task }
+                    AccountMeta::new(authority, true),
+                    AccountMeta::new(update_pda, false),
+                    // Delete { authority, task }
+                    AccountMeta::new(authority, true),
+                    AccountMeta::new(delete_pda, false),
+                ],
+                data: batch_ix_data,
+            }],
+            Some(&ctx.payer.pubkey()),
+            &[&authority_keypair, &ctx.payer.insecure_clone()],
+            ctx.last_blockhash,
+        );
+
+        ctx.banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let created = ctx
+            .banks_client
+            .get_account_data_with_borsh::<Task>(create_pda)
+            .await
+            .unwrap();
+        assert_eq!(create_id, created.id);
+
+        let updated = ctx
+            .banks_client
+            .get_account_data_with_borsh::<Task>(update_pda)
+            .await
+            .unwrap();
+        assert_eq!(new_title, updated.title);
+        assert_eq!(new_description, updated.description);
+
+        let deleted = ctx.banks_client.get_account(delete_pda).await.unwrap();
+        assert!(deleted.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_batch_tasks_rolls_back_on_failure() {
+        let program_id = Pubkey::new_unique();
+        let (title, description, new_title, new_description) = get_env();
+
+        let update_id: u64 = 1;
+        let bad_delete_id: u64 = 99;
+
+        let authority_keypair = Keypair::new();
+        let authority = authority_keypair.pubkey();
+
+        let mut test = ProgramTest::default();
+        test.add_program("todo", program_id, None);
+
+        let (update_pda, update_bump) = Pubkey::find_program_address(
+            &[
+                Task::TAG.as_bytes(),
+                &update_id.to_le_bytes(),
+                authority.as_ref(),
+            ],
+            &program_id,
+        );
+        let update_task = Task::new(
+            update_id,
+            title,
+            description,
+            authority,
+            update_bump,
+            0,
+            vec![],
+        );
+        let mut update_account_data = Vec::new();
+        update_task.serialize(&mut update_account_data).unwrap();
+        test.add_account(
+            update_pda,
+            Account {
+                lamports: u32::MAX as u64,
+                owner: program_id,
+                executable: false,
+                rent_epoch: Default::default(),
+                data: update_account_data,
+            },
+        );
+
+        let (bad_delete_pda, _) = Pubkey::find_program_address(
+            &[
+                Task::TAG.as_bytes(),
+                &bad_delete_id.to_le_bytes(),
+                authority.as_ref(),
+            ],
+            &program_id,
+        );
+
+        let ctx = test.start_with_context().await;
+
+        // The delete op targets a PDA that was never created, so it will
+        // fail the Task owner check and the whole batch (including the otherwise
+        // valid update) must not be applied.
+        let batch_ix = TodoInstruction::BatchTasks {
+            ops: vec![
+                TaskOp::Update {
+                    id: update_id,
+                    title: Some(new_title),
+                    description: Some(new_description),
+                },
+                TaskOp::Delete { id: bad_delete_id },
+            ],
+        };
+
+        let mut batch_ix_data = Vec::new();
+        batch_ix.serialize(&mut batch_ix_data).unwrap();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new(authority, true),
+                    AccountMeta::new(update_pda, false),
+                    AccountMeta::new(authority, true),
+                    AccountMeta::new(bad_delete_pda, false),
+                ],
+                data: batch_ix_data,
+            }],
+            Some(&ctx.payer.pubkey()),
+            &[&authority_keypair, &ctx.payer.insecure_clone()],
+            ctx.last_blockhash,
+        );
+
+        assert!(ctx
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .is_err());
+
+        // The update must not have been applied either.
+        let task = ctx
+            .banks_client
+            .get_account_data_with_borsh::<Task>(update_pda)
+            .await
+            .unwrap();
+        assert_eq!(title, task.title);
+        assert_eq!(description, task.description);
+    }
+
+    #[tokio::test]
+    async fn test_batch_tasks_duplicate_task_account_no_panic() {
+        let program_id = Pubkey::new_unique();
+        let (title, description, new_title, new_description) = get_env();
+
+        let id: u64 = 1;
+
+        let authority_keypair = Keypair::new();
+        let authority = authority_keypair.pubkey();
+
+        let (task_pda, task_bump) = Pubkey::find_program_address(
+            &[Task::TAG.as_bytes(), &id.to_le_bytes(), authority.as_ref()],
+            &program_id,
+        );
+        let existing_task = Task::new(id, title, description, authority, task_bump, 0, vec![]);
+        let mut account_data: Vec<u8> = Vec::new();
+        existing_task.serialize(&mut account_data).unwrap();
+
+        let mut test = ProgramTest::default();
+        test.add_program("todo", program_id, None);
+        test.add_account(
+            task_pda,
+            Account {
+                lamports: u32::MAX as u64,
+                owner: program_id,
+                executable: false,
+                rent_epoch: Default::default(),
+                data: account_data,
+            },
+        );
+        let ctx = test.start_with_context().await;
+
+        // Both ops target the very same Task PDA (and the same authority):
+        // first an Update, then a Delete. The account list below repeats
+        // `task_pda`/`authority` across both ops' account ranges, which
+        // would double-borrow a naive implementation.
+        let batch_ix = TodoInstruction::BatchTasks {
+            ops: vec![
+                TaskOp::Update {
+                    id,
+                    title: Some(new_title),
+                    description: Some(new_description),
+                },
+                TaskOp::Delete { id },
+            ],
+        };
+
+        let mut batch_ix_data = Vec::new();
+        batch_ix.serialize(&mut batch_ix_data).unwrap();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: vec![
+                    // Update { authority, task }
+                    AccountMeta::new(authority, true),
+                    AccountMeta::new(task_pda, false),
+                    // Delete { authority, task }
+                    AccountMeta::new(authority, true),
+                    AccountMeta::new(task_pda, false),
+                ],
+                data: batch_ix_data,
+            }],
+            Some(&ctx.payer.pubkey()),
+            &[&authority_keypair, &ctx.payer.insecure_clone()],
+            ctx.last_blockhash,
+        );
+
+        ctx.banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        // The Update must have been visible to the Delete (same buffer),
+        // and the account must end up closed with no lamports stranded.
+        let task_account = ctx.banks_client.get_account(task_pda).await.unwrap();
+        assert!(task_account.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_write_task_at_offset() {
+        let program_id = Pubkey::new_unique();
+        let id: u64 = 1;
+        let (title, description, _, _) = get_env();
+        let authority_keypair = Keypair::new();
+        let authority = authority_keypair.pubkey();
+
+        let (task_pda, task_bump) = Pubkey::find_program_address(
+            &[Task::TAG.as_bytes(), &id.to_le_bytes(), authority.as_ref()],
+            &program_id,
+        );
+
+        // Inject Account that simulates CreateTask.
+        let old_task = Task::new(id, title, description, authority, task_bump, 0, vec![]);
+        let mut account_data: Vec<u8> = Vec::new();
+        old_task.serialize(&mut account_data).unwrap();
+        let injected_task_account = Account {
+            lamports: u32::MAX as u64,
+            owner: program_id,
+            executable: false,
+            rent_epoch: Default::default(),
+            data: account_data,
+        };
+
+        let mut test = ProgramTest::default();
+        test.add_program("todo", program_id, None);
+        test.add_account(task_pda, injected_task_account);
+        let ctx = test.start_with_context().await;
+
+        // Write into the description field (offset 64, past the 64-byte
+        // title) without touching the rest of the content.
+        let patch = b"patched".to_vec();
+        let write_task_ix = TodoInstruction::WriteTask {
+            id,
+            offset: 64,
+            data: patch.clone(),
+        };
+
+        let mut write_task_ix_data = Vec::new();
+        write_task_ix.serialize(&mut write_task_ix_data).unwrap();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new(authority, true),
+                    AccountMeta::new(task_pda, false),
+                ],
+                data: write_task_ix_data,
+            }],
+            Some(&ctx.payer.pubkey()),
+            &[&authority_keypair, &ctx.payer.insecure_clone()],
+            ctx.last_blockhash,
+        );
+
+        ctx.banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let task = ctx
+            .banks_client
+            .get_account_data_with_borsh::<Task>(task_pda)
+            .await
+            .unwrap();
+
+        assert_eq!(title, task.title);
+        assert_eq!(&patch[..], &task.description[..patch.len()]);
+        assert_eq!(authority, task.authority);
+        assert_eq!(id, task.id);
+    }
+
+    #[tokio::test]
+    async fn test_write_task_out_of_bounds_rejected() {
+        let program_id = Pubkey::new_unique();
+        let id: u64 = 1;
+        let (title, description, _, _) = get_env();
+        let authority_keypair = Keypair::new();
+        let authority = authority_keypair.pubkey();
+
+        let (task_pda, task_bump) = Pubkey::find_program_address(
+            &[Task::TAG.as_bytes(), &id.to_le_bytes(), authority.as_ref()],
+            &program_id,
+        );
+
+        let old_task = Task::new(id, title, description, authority, task_bump, 0, vec![]);
+        let mut account_data: Vec<u8> = Vec::new();
+        old_task.serialize(&mut account_data).unwrap();
+        let injected_task_account = Account {
+            lamports: u32::MAX as u64,
+            owner: program_id,
+            executable: false,
+            rent_epoch: Default::default(),
+            data: account_data,
+        };
+
+        let mut test = ProgramTest::default();
+        test.add_program("todo", program_id, None);
+        test.add_account(task_pda, injected_task_account);
+        let ctx = test.start_with_context().await;
+
+        // Content region is only 128 bytes (title + description); writing
+        // past it must be rejected rather than clobbering neighboring
+        // account memory.
+        let write_task_ix = TodoInstruction::WriteTask {
+            id,
+            offset: 127,
+            data: vec![0u8; 8],
+        };
+
+        let mut write_task_ix_data = Vec::new();
+        write_task_ix.serialize(&mut write_task_ix_data).unwrap();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new(authority, true),
+                    AccountMeta::new(task_pda, false),
+                ],
+                data: write_task_ix_data,
+            }],
+            Some(&ctx.payer.pubkey()),
+            &[&authority_keypair, &ctx.payer.insecure_clone()],
+            ctx.last_blockhash,
+        );
+
+        assert!(ctx
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_write_task_rejects_body_length_prefix() {
+        let program_id = Pubkey::new_unique();
+        let id: u64 = 1;
+        let (title, description, _, _) = get_env();
+        let authority_keypair = Keypair::new();
+        let authority = authority_keypair.pubkey();
+
+        let (task_pda, task_bump) = Pubkey::find_program_address(
+            &[Task::TAG.as_bytes(), &id.to_le_bytes(), authority.as_ref()],
+            &program_id,
+        );
+
+        let old_task = Task::new(id, title, description, authority, task_bump, 0, vec![]);
+        let mut account_data: Vec<u8> = Vec::new();
+        old_task.serialize(&mut account_data).unwrap();
+        let injected_task_account = Account {
+            lamports: u32::MAX as u64,
+            owner: program_id,
+            executable: false,
+            rent_epoch: Default::default(),
+            data: account_data,
+        };
+
+        let mut test = ProgramTest::default();
+        test.add_program("todo", program_id, None);
+        test.add_account(task_pda, injected_task_account);
+        let ctx = test.start_with_context().await;
+
+        // `body`'s own Borsh length prefix sits right after the 128-byte
+        // content region, for every task regardless of body length.
+        // Landing a write there (rather than being shifted into `body`'s
+        // data) would corrupt the prefix and permanently brick the account.
+        let write_task_ix = TodoInstruction::WriteTask {
+            id,
+            offset: Task::CONTENT_LEN as u64,
+            data: vec![0xFFu8; 4],
+        };
+
+        let mut write_task_ix_data = Vec::new();
+        write_task_ix.serialize(&mut write_task_ix_data).unwrap();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new(authority, true),
+                    AccountMeta::new(task_pda, false),
+                ],
+                data: write_task_ix_data,
+            }],
+            Some(&ctx.payer.pubkey()),
+            &[&authority_keypair, &ctx.payer.insecure_clone()],
+            ctx.last_blockhash,
+        );
+
+        assert!(ctx
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .is_err());
+
+        // The account must still parse cleanly afterwards.
+        let task = ctx
+            .banks_client
+            .get_account_data_with_borsh::<Task>(task_pda)
+            .await
+            .unwrap();
+        assert_eq!(title, task.title);
+        assert_eq!(description, task.description);
+        assert!(task.body.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_resize_task_grows_and_shrinks() {
+        let program_id = Pubkey::new_unique();
+        let id: u64 = 1;
+        let (title, description, _, _) = get_env();
+        let authority_keypair = Keypair::new();
+        let authority = authority_keypair.pubkey();
+
+        let (task_pda, task_bump) = Pubkey::find_program_address(
+            &[Task::TAG.as_bytes(), &id.to_le_bytes(), authority.as_ref()],
+            &program_id,
+        );
+
+        // Body starts with 64 bytes of room so a marker can be written into
+        // it before any resize, to prove resizing preserves existing data.
+        let old_task = Task::new(
+            id,
+            title,
+            description,
+            authority,
+            task_bump,
+            0,
+            vec![0u8; 64],
+        );
+        let mut account_data: Vec<u8> = Vec::new();
+        old_task.serialize(&mut account_data).unwrap();
+        let injected_task_account = Account {
+            lamports: Rent::default().minimum_balance(account_data.len()),
+            owner: program_id,
+            executable: false,
+            rent_epoch: Default::default(),
+            data: account_data,
+        };
+
+        let mut test = ProgramTest::default();
+        test.add_program("todo", program_id, None);
+        test.add_account(task_pda, injected_task_account);
+        // The authority pays the rent top-up when the body grows.
+        test.add_account(
+            authority,
+            Account {
+                lamports: u32::MAX as u64,
+                owner: SYSTEM_PROGRAM_ID,
+                executable: false,
+                rent_epoch: Default::default(),
+                data: vec![],
+            },
+        );
+        let ctx = test.start_with_context().await;
+
+        // Mark the start of `body` so grow/shrink can be checked for data
+        // preservation, not just length.
+        let marker = b"MARKER!!".to_vec();
+        let write_marker_ix = TodoInstruction::WriteTask {
+            id,
+            offset: Task::CONTENT_LEN as u64,
+            data: marker.clone(),
+        };
+        let mut write_marker_ix_data = Vec::new();
+        write_marker_ix
+            .serialize(&mut write_marker_ix_data)
+            .unwrap();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new(authority, true),
+                    AccountMeta::new(task_pda, false),
+                ],
+                data: write_marker_ix_data,
+            }],
+            Some(&ctx.payer.pubkey()),
+            &[&authority_keypair, &ctx.payer.insecure_clone()],
+            ctx.last_blockhash,
+        );
+        ctx.banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        // Grow the body to 256 bytes.
+        let grow_ix = TodoInstruction::ResizeTask { id, new_len: 256 };
+        let mut grow_ix_data = Vec::new();
+        grow_ix.serialize(&mut grow_ix_data).unwrap();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new(authority, true),
+                    AccountMeta::new(task_pda, false),
+                    AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                ],
+                data: grow_ix_data,
+            }],
+            Some(&ctx.payer.pubkey()),
+            &[&authority_keypair, &ctx.payer.insecure_clone()],
+            ctx.last_blockhash,
+        );
+        ctx.banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let grown_account = ctx
+            .banks_client
+            .get_account(task_pda)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(Task::LEN + 256, grown_account.data.len());
+        assert_eq!(
+            Rent::default().minimum_balance(grown_account.data.len()),
+            grown_account.lamports
+        );
+
+        let grown = ctx
+            .banks_client
+            .get_account_data_with_borsh::<Task>(task_pda)
+            .await
+            .unwrap();
+        assert_eq!(title, grown.title);
+        assert_eq!(description, grown.description);
+        assert_eq!(256, grown.body.len());
+        assert_eq!(marker, grown.body[..marker.len()]);
+
+        // Shrink the body back to 32 bytes.
+        let shrink_ix = TodoInstruction::ResizeTask { id, new_len: 32 };
+        let mut shrink_ix_data = Vec::new();
+        shrink_ix.serialize(&mut shrink_ix_data).unwrap();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new(authority, true),
+                    AccountMeta::new(task_pda, false),
+                    AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                ],
+                data: shrink_ix_data,
+            }],
+            Some(&ctx.payer.pubkey()),
+            &[&authority_keypair, &ctx.payer.insecure_clone()],
+            ctx.last_blockhash,
+        );
+        ctx.banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let shrunk_account = ctx
+            .banks_client
+            .get_account(task_pda)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(Task::LEN + 32, shrunk_account.data.len());
+        assert_eq!(
+            Rent::default().minimum_balance(shrunk_account.data.len()),
+            shrunk_account.lamports
+        );
+
+        let shrunk = ctx
+            .banks_client
+            .get_account_data_with_borsh::<Task>(task_pda)
+            .await
+            .unwrap();
+        assert_eq!(title, shrunk.title);
+        assert_eq!(description, shrunk.description);
+        assert_eq!(32, shrunk.body.len());
+        assert_eq!(marker, shrunk.body[..marker.len()]);
+    }
+
+    #[tokio::test]
+    async fn test_create_task_rejects_reinitialization() {
+        let program_id = Pubkey::new_unique();
+        let id: u64 = 1;
+        let (title, description, _, _) = get_env();
+        let authority_keypair = Keypair::new();
+        let authority = authority_keypair.pubkey();
+
+        let (task_pda, task_bump) = Pubkey::find_program_address(
+            &[Task::TAG.as_bytes(), &id.to_le_bytes(), authority.as_ref()],
+            &program_id,
+        );
+
+        // Simulate an attacker-supplied Task PDA that is already initialized.
+        let existing_task = Task::new(id, title, description, authority, task_bump, 0, vec![]);
+        let mut account_data: Vec<u8> = Vec::new();
+        existing_task.serialize(&mut account_data).unwrap();
+        let injected_task_account = Account {
+            lamports: u32::MAX as u64,
+            owner: program_id,
+            executable: false,
+            rent_epoch: Default::default(),
+            data: account_data,
+        };
+
+        let mut test = ProgramTest::default();
+        test.add_program("todo", program_id, None);
+        test.add_account(task_pda, injected_task_account);
+        let ctx = test.start_with_context().await;
+
+        let create_task_ix = TodoInstruction::CreateTask {
+            id,
+            title,
+            description,
+            bump: task_bump,
+            body_len: 0,
+        };
+
+        let mut create_task_ix_data = Vec::new();
+        create_task_ix.serialize(&mut create_task_ix_data).unwrap();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new(authority, true),
+                    AccountMeta::new(task_pda, false),
+                    AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                ],
+                data: create_task_ix_data,
+            }],
+            Some(&ctx.payer.pubkey()),
+            &[&authority_keypair, &ctx.payer.insecure_clone()],
+            ctx.last_blockhash,
+        );
+
+        assert!(ctx
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_update_task_rejects_wrong_owner() {
+        let program_id = Pubkey::new_unique();
+        let id: u64 = 1;
+        let (title, description, new_title, new_description) = get_env();
+        let authority_keypair = Keypair::new();
+        let authority = authority_keypair.pubkey();
+
+        let (task_pda, task_bump) = Pubkey::find_program_address(
+            &[Task::TAG.as_bytes(), &id.to_le_bytes(), authority.as_ref()],
+            &program_id,
+        );
+
+        let old_task = Task::new(id, title, description, authority, task_bump, 0, vec![]);
+        let mut account_data: Vec<u8> = Vec::new();
+        old_task.serialize(&mut account_data).unwrap();
+        let injected_task_account = Account {
+            lamports: u32::MAX as u64,
+            owner: Pubkey::new_unique(), // Not owned by the Todo program.
+            executable: false,
+            rent_epoch: Default::default(),
+            data: account_data,
+        };
+
+        let mut test = ProgramTest::default();
+        test.add_program("todo", program_id, None);
+        test.add_account(task_pda, injected_task_account);
+        let ctx = test.start_with_context().await;
+
+        let update_task_ix = TodoInstruction::UpdateTask {
+            id,
+            title: Some(new_title),
+            description: Some(new_description),
+        };
+
+        let mut update_task_ix_data = Vec::new();
+        update_task_ix.serialize(&mut update_task_ix_data).unwrap();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new(authority, true),
+                    AccountMeta::new(task_pda, false),
+                ],
+                data: update_task_ix_data,
+            }],
+            Some(&ctx.payer.pubkey()),
+            &[&authority_keypair, &ctx.payer.insecure_clone()],
+            ctx.last_blockhash,
+        );
+
+        assert!(ctx
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_update_task_rejects_spoofed_discriminator() {
+        let program_id = Pubkey::new_unique();
+        let id: u64 = 1;
+        let (title, description, new_title, new_description) = get_env();
+        let authority_keypair = Keypair::new();
+        let authority = authority_keypair.pubkey();
+
+        let (task_pda, task_bump) = Pubkey::find_program_address(
+            &[Task::TAG.as_bytes(), &id.to_le_bytes(), authority.as_ref()],
+            &program_id,
+        );
+
+        let old_task = Task::new(id, title, description, authority, task_bump, 0, vec![]);
+        let mut account_data: Vec<u8> = Vec::new();
+        old_task.serialize(&mut account_data).unwrap();
+        // Corrupt the discriminator so the data no longer looks like a Task,
+        // even though the owner and lamports are otherwise legitimate.
+        account_data[0] ^= 0xFF;
+        let injected_task_account = Account {
+            lamports: u32::MAX as u64,
+            owner: program_id,
+            executable: false,
+            rent_epoch: Default::default(),
+            data: account_data,
+        };
+
+        let mut test = ProgramTest::default();
+        test.add_program("todo", program_id, None);
+        test.add_account(task_pda, injected_task_account);
+        let ctx = test.start_with_context().await;
+
+        let update_task_ix = TodoInstruction::UpdateTask {
+            id,
+            title: Some(new_title),
+            description: Some(new_description),
+        };
+
+        let mut update_task_ix_data = Vec::new();
+        update_task_ix.serialize(&mut update_task_ix_data).unwrap();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new(authority, true),
+                    AccountMeta::new(task_pda, false),
+                ],
+                data: update_task_ix_data,
+            }],
+            Some(&ctx.payer.pubkey()),
+            &[&authority_keypair, &ctx.payer.insecure_clone()],
+            ctx.last_blockhash,
+        );
+
+        assert!(ctx
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_deserialize_versioned_parses_unmigrated_v0_account() {
+        let program_id = Pubkey::new_unique();
+        let id: u64 = 1;
+        let (title, description, _, _) = get_env();
+        let authority = Pubkey::new_unique();
+        let (_, task_bump) = Pubkey::find_program_address(
+            &[Task::TAG.as_bytes(), &id.to_le_bytes(), authority.as_ref()],
+            &program_id,
+        );
+
+        let legacy_task = TaskV0 {
+            discriminator: Task::DISCRIMINATOR,
+            version: 0,
+            authority,
+            id,
+            bump: task_bump,
+            title,
+            description,
+            body: vec![],
+        };
+        let mut legacy_bytes: Vec<u8> = Vec::new();
+        legacy_task.serialize(&mut legacy_bytes).unwrap();
+
+        let parsed = Task::deserialize_versioned(&legacy_bytes).unwrap();
+        assert_eq!(0, parsed.version);
+        assert_eq!(0, parsed.created_at);
+        assert!(!parsed.completed);
+        assert_eq!(authority, parsed.authority);
+        assert_eq!(id, parsed.id);
+        assert_eq!(title, parsed.title);
+        assert_eq!(description, parsed.description);
+    }
+
+    #[tokio::test]
+    async fn test_migrate_task_upgrades_v0_account() {
+        let program_id = Pubkey::new_unique();
+        let id: u64 = 1;
+        let (title, description, _, _) = get_env();
+        let authority_keypair = Keypair::new();
+        let authority = authority_keypair.pubkey();
+
+        let (task_pda, task_bump) = Pubkey::find_program_address(
+            &[Task::TAG.as_bytes(), &id.to_le_bytes(), authority.as_ref()],
+            &program_id,
+        );
+
+        let legacy_task = TaskV0 {
+            discriminator: Task::DISCRIMINATOR,
+            version: 0,
+            authority,
+            id,
+            bump: task_bump,
+            title,
+            description,
+            body: vec![],
+        };
+        let mut account_data: Vec<u8> = Vec::new();
+        legacy_task.serialize(&mut account_data).unwrap();
+        let injected_task_account = Account {
+            lamports: Rent::default().minimum_balance(account_data.len()),
+            owner: program_id,
+            executable: false,
+            rent_epoch: Default::default(),
+            data: account_data,
+        };
+
+        let mut test = ProgramTest::default();
+        test.add_program("todo", program_id, None);
+        test.add_account(task_pda, injected_task_account);
+        // The authority pays the rent top-up when the account grows to the
+        // current (larger) layout.
+        test.add_account(
+            authority,
+            Account {
+                lamports: u32::MAX as u64,
+                owner: SYSTEM_PROGRAM_ID,
+                executable: false,
+                rent_epoch: Default::default(),
+                data: vec![],
+            },
+        );
+        let ctx = test.start_with_context().await;
+
+        let migrate_ix = TodoInstruction::MigrateTask { id };
+        let mut migrate_ix_data = Vec::new();
+        migrate_ix.serialize(&mut migrate_ix_data).unwrap();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new(authority, true),
+                    AccountMeta::new(task_pda, false),
+                    AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                ],
+                data: migrate_ix_data,
+            }],
+            Some(&ctx.payer.pubkey()),
+            &[&authority_keypair, &ctx.payer.insecure_clone()],
+            ctx.last_blockhash,
+        );
+        ctx.banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let migrated_account = ctx
+            .banks_client
+            .get_account(task_pda)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(Task::LEN, migrated_account.data.len());
+        assert_eq!(
+            Rent::default().minimum_balance(migrated_account.data.len()),
+            migrated_account.lamports
+        );
+
+        let migrated = ctx
+            .banks_client
+            .get_account_data_with_borsh::<Task>(task_pda)
+            .await
+            .unwrap();
+        assert_eq!(Task::CURRENT_VERSION, migrated.version);
+        assert_eq!(0, migrated.created_at);
+        assert!(!migrated.completed);
+        assert_eq!(title, migrated.title);
+        assert_eq!(description, migrated.description);
+        assert_eq!(authority, migrated.authority);
+        assert_eq!(id, migrated.id);
+    }
+
     //------------------------------------------------------------
     // Other Testing
     //------------------------------------------------------------
@@ -496,7 +2053,7 @@ mod tests {
         );
 
         // Inject Account that simulates CreateTask.
-        let old_task = Task::new(id, title, description, authority, task_bump);
+        let old_task = Task::new(id, title, description, authority, task_bump, 0, vec![]);
         let mut account_data: Vec<u8> = Vec::new();
         old_task.serialize(&mut account_data).unwrap();
         let injected_task_account = Account {